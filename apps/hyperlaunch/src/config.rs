@@ -0,0 +1,220 @@
+//! User-configurable search scope, modeled on czkawka's `Directories`/
+//! `ExcludedItems`: which roots to search, which directories/extensions to
+//! skip, and the depth/size/hidden-file limits. Loaded from (and saved to)
+//! a TOML file under `dirs::config_dir()`, then compiled once into a
+//! `CompiledFilter` so neither walker has to re-parse globs per entry.
+
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use globset::{Glob, GlobSet, GlobSetBuilder};
+use serde::{Deserialize, Serialize};
+
+fn config_path() -> PathBuf {
+    dirs::config_dir()
+        .unwrap_or_else(std::env::temp_dir)
+        .join("hyperlaunch")
+        .join("config.toml")
+}
+
+fn default_excluded_dir_names() -> Vec<String> {
+    [
+        "node_modules", ".cargo", "target", "build", "dist",
+        ".npm", ".cache", "__pycache__", ".venv", "venv",
+        ".git", ".gradle", ".m2", ".ivy2", "pkg",
+        "vendor", "deps", "Pods", ".tox", ".pytest_cache",
+    ]
+    .iter()
+    .map(|s| s.to_string())
+    .collect()
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(default)]
+pub(crate) struct SearchConfig {
+    /// Search roots. Empty means "just the home directory".
+    pub included_dirs: Vec<PathBuf>,
+    /// Absolute directory paths to prune entirely.
+    pub excluded_dirs: Vec<PathBuf>,
+    /// Directory basenames to prune wherever they're encountered.
+    pub excluded_dir_names: Vec<String>,
+    /// Glob patterns (matched against the full path) to exclude.
+    pub excluded_globs: Vec<String>,
+    /// If non-empty, only files with one of these extensions are kept.
+    pub allowed_extensions: Vec<String>,
+    /// Extensions to always skip, regardless of `allowed_extensions`.
+    pub excluded_extensions: Vec<String>,
+    pub max_depth: usize,
+    pub max_content_file_size: u64,
+    pub include_hidden: bool,
+    /// Whether to decode and downscale image file results into thumbnail
+    /// `icon_data`. Users on slow disks can turn this off.
+    pub generate_thumbnails: bool,
+    /// Source images larger than this are skipped rather than decoded, so a
+    /// single giant image can't stall a search.
+    pub thumbnail_max_source_size: u64,
+}
+
+impl Default for SearchConfig {
+    fn default() -> Self {
+        SearchConfig {
+            included_dirs: Vec::new(),
+            excluded_dirs: Vec::new(),
+            excluded_dir_names: default_excluded_dir_names(),
+            excluded_globs: Vec::new(),
+            allowed_extensions: Vec::new(),
+            excluded_extensions: Vec::new(),
+            max_depth: 4,
+            max_content_file_size: 500_000,
+            include_hidden: true,
+            generate_thumbnails: true,
+            thumbnail_max_source_size: 10_000_000,
+        }
+    }
+}
+
+impl SearchConfig {
+    /// Loads the saved config. If none exists yet, the default is persisted
+    /// and returned; if it fails to parse, the default is returned and the
+    /// file on disk is left as it is.
+    pub fn load() -> Self {
+        match fs::read_to_string(config_path()) {
+            Ok(text) => toml::from_str(&text).unwrap_or_default(),
+            Err(_) => {
+                let config = SearchConfig::default();
+                config.save();
+                config
+            }
+        }
+    }
+
+    pub fn save(&self) {
+        let path = config_path();
+        if let Some(parent) = path.parent() {
+            let _ = fs::create_dir_all(parent);
+        }
+        if let Ok(text) = toml::to_string_pretty(self) {
+            let _ = fs::write(path, text);
+        }
+    }
+}
+
+/// A `SearchConfig` compiled into a form both walkers can consult cheaply:
+/// globs are pre-parsed into a `GlobSet` and the name/extension lists into
+/// hash sets, so matching an entry is O(1)-ish instead of re-parsing config
+/// on every directory entry.
+pub(crate) struct CompiledFilter {
+    roots: Vec<PathBuf>,
+    excluded_dirs: HashSet<PathBuf>,
+    excluded_dir_names: HashSet<String>,
+    excluded_globs: GlobSet,
+    allowed_extensions: HashSet<String>,
+    excluded_extensions: HashSet<String>,
+    max_depth: usize,
+    max_content_file_size: u64,
+    include_hidden: bool,
+    generate_thumbnails: bool,
+    thumbnail_max_source_size: u64,
+}
+
+impl CompiledFilter {
+    pub fn from_config(config: &SearchConfig) -> Self {
+        let mut builder = GlobSetBuilder::new();
+        for pattern in &config.excluded_globs {
+            if let Ok(glob) = Glob::new(pattern) {
+                builder.add(glob);
+            }
+        }
+        let excluded_globs = builder.build().unwrap_or_else(|_| GlobSetBuilder::new().build().unwrap());
+
+        CompiledFilter {
+            roots: config.included_dirs.clone(),
+            excluded_dirs: config.excluded_dirs.iter().cloned().collect(),
+            excluded_dir_names: config.excluded_dir_names.iter().cloned().collect(),
+            excluded_globs,
+            allowed_extensions: config
+                .allowed_extensions
+                .iter()
+                .map(|e| e.to_lowercase())
+                .collect(),
+            excluded_extensions: config
+                .excluded_extensions
+                .iter()
+                .map(|e| e.to_lowercase())
+                .collect(),
+            max_depth: config.max_depth,
+            max_content_file_size: config.max_content_file_size,
+            include_hidden: config.include_hidden,
+            generate_thumbnails: config.generate_thumbnails,
+            thumbnail_max_source_size: config.thumbnail_max_source_size,
+        }
+    }
+
+    pub fn roots(&self) -> Vec<PathBuf> {
+        if self.roots.is_empty() {
+            vec![dirs::home_dir().unwrap_or_else(|| PathBuf::from("/"))]
+        } else {
+            self.roots.clone()
+        }
+    }
+
+    pub fn max_depth(&self) -> usize {
+        self.max_depth
+    }
+
+    pub fn include_hidden(&self) -> bool {
+        self.include_hidden
+    }
+
+    pub fn max_content_file_size(&self) -> u64 {
+        self.max_content_file_size
+    }
+
+    pub fn generate_thumbnails(&self) -> bool {
+        self.generate_thumbnails
+    }
+
+    pub fn thumbnail_max_source_size(&self) -> u64 {
+        self.thumbnail_max_source_size
+    }
+
+    /// Whether `path` should be kept, given whether it's a directory. Used
+    /// both by the `ignore::WalkBuilder::filter_entry` callback and by the
+    /// plain `fs::read_dir` based incremental refresh.
+    pub fn allows_path(&self, path: &Path, is_dir: bool) -> bool {
+        let Some(name) = path.file_name().and_then(|n| n.to_str()) else {
+            return true;
+        };
+
+        if !self.include_hidden && name.starts_with('.') {
+            return false;
+        }
+
+        if is_dir {
+            if self.excluded_dir_names.contains(name) {
+                return false;
+            }
+            if self.excluded_dirs.contains(path) {
+                return false;
+            }
+        } else {
+            let ext = path.extension().and_then(|e| e.to_str()).map(|e| e.to_lowercase());
+            if ext.as_ref().is_some_and(|e| self.excluded_extensions.contains(e)) {
+                return false;
+            }
+            if !self.allowed_extensions.is_empty()
+                && !ext.as_ref().is_some_and(|e| self.allowed_extensions.contains(e))
+            {
+                return false;
+            }
+        }
+
+        !self.excluded_globs.is_match(path)
+    }
+
+    pub fn allows_entry(&self, entry: &ignore::DirEntry) -> bool {
+        let is_dir = entry.file_type().map(|t| t.is_dir()).unwrap_or(false);
+        self.allows_path(entry.path(), is_dir)
+    }
+}
@@ -0,0 +1,154 @@
+//! fzf-v2-style fuzzy matcher: a dynamic-programming alignment over
+//! (needle chars x haystack chars) that rewards word-boundary starts and
+//! consecutive runs while penalizing gaps, and backtracks through the
+//! matrix to report which haystack positions were actually matched so the
+//! UI can highlight them.
+
+const SCORE_MATCH: i64 = 16;
+const BONUS_BOUNDARY: i64 = 8;
+const BONUS_CAMEL_CASE: i64 = 12;
+const BONUS_CONSECUTIVE: i64 = 4;
+const MAX_CONSECUTIVE_BONUS_RUN: i64 = 8;
+const PENALTY_GAP_START: i64 = -3;
+const PENALTY_GAP_EXTENSION: i64 = -1;
+const NEG_INF: i64 = i64::MIN / 2;
+
+#[derive(Clone, Copy, PartialEq)]
+enum CharClass {
+    Lower,
+    Upper,
+    Digit,
+    NonWord,
+}
+
+fn char_class(c: char) -> CharClass {
+    if c.is_ascii_digit() {
+        CharClass::Digit
+    } else if c.is_uppercase() {
+        CharClass::Upper
+    } else if c.is_lowercase() {
+        CharClass::Lower
+    } else {
+        CharClass::NonWord
+    }
+}
+
+fn boundary_bonus(prev: CharClass, cur: CharClass) -> i64 {
+    if prev == CharClass::Lower && cur == CharClass::Upper {
+        BONUS_CAMEL_CASE
+    } else if prev == CharClass::NonWord && cur != CharClass::NonWord {
+        BONUS_BOUNDARY
+    } else {
+        0
+    }
+}
+
+/// Scores `needle` as a fuzzy subsequence of `hay` and reports which char
+/// indices (not byte offsets) in `hay` were matched. Returns `None` when
+/// `needle` isn't a subsequence of `hay` at all.
+pub fn fuzzy_match(hay: &str, needle: &str) -> Option<(f64, Vec<usize>)> {
+    if needle.is_empty() {
+        return Some((0.0, Vec::new()));
+    }
+
+    let hay_chars: Vec<char> = hay.chars().collect();
+    let needle_chars: Vec<char> = needle.chars().collect();
+    let n = hay_chars.len();
+    let m = needle_chars.len();
+    if m > n {
+        return None;
+    }
+
+    // `to_lowercase()` can expand a single char into several codepoints (e.g.
+    // Turkish 'İ' -> "i̇"), which would misalign these against `hay_chars`/
+    // `needle_chars` if flattened. Take just the first lowered codepoint per
+    // char instead, so the vectors stay one-to-one with their char arrays.
+    let hay_lower: Vec<char> = hay_chars.iter().map(|c| c.to_lowercase().next().unwrap()).collect();
+    let needle_lower: Vec<char> = needle_chars
+        .iter()
+        .map(|c| c.to_lowercase().next().unwrap())
+        .collect();
+
+    let mut bonus = vec![0i64; n];
+    let mut prev_class = CharClass::NonWord;
+    for (i, &c) in hay_chars.iter().enumerate() {
+        let class = char_class(c);
+        bonus[i] = boundary_bonus(prev_class, class);
+        prev_class = class;
+    }
+
+    // score[j][i] / consecutive[j][i] / gap[j][i] are 1-indexed on both axes;
+    // row/col 0 is the "nothing matched yet" base case.
+    let mut score = vec![vec![NEG_INF; n + 1]; m + 1];
+    let mut consecutive = vec![vec![0i64; n + 1]; m + 1];
+    let mut gap = vec![vec![0i64; n + 1]; m + 1];
+    let mut from_match = vec![vec![false; n + 1]; m + 1];
+
+    for row in score[0].iter_mut() {
+        *row = 0;
+    }
+
+    for j in 1..=m {
+        for i in 1..=n {
+            let skip_score = if score[j][i - 1] > NEG_INF {
+                let penalty = if gap[j][i - 1] == 0 {
+                    PENALTY_GAP_START
+                } else {
+                    PENALTY_GAP_EXTENSION
+                };
+                Some(score[j][i - 1] + penalty)
+            } else {
+                None
+            };
+
+            let match_candidate = if hay_lower[i - 1] == needle_lower[j - 1] && score[j - 1][i - 1] > NEG_INF {
+                let run = consecutive[j - 1][i - 1] + 1;
+                let consec_bonus = (run - 1).min(MAX_CONSECUTIVE_BONUS_RUN) * BONUS_CONSECUTIVE;
+                Some((score[j - 1][i - 1] + SCORE_MATCH + bonus[i - 1] + consec_bonus, run))
+            } else {
+                None
+            };
+
+            match (match_candidate, skip_score) {
+                (Some((m_score, run)), Some(s_score)) if m_score >= s_score => {
+                    score[j][i] = m_score;
+                    consecutive[j][i] = run;
+                    from_match[j][i] = true;
+                }
+                (Some((m_score, run)), None) => {
+                    score[j][i] = m_score;
+                    consecutive[j][i] = run;
+                    from_match[j][i] = true;
+                }
+                (_, Some(s_score)) => {
+                    score[j][i] = s_score;
+                    gap[j][i] = gap[j][i - 1] + 1;
+                }
+                (None, None) => {}
+            }
+        }
+    }
+
+    let (best_i, best_score) = (1..=n)
+        .map(|i| (i, score[m][i]))
+        .max_by_key(|&(_, s)| s)?;
+    if best_score <= NEG_INF {
+        return None;
+    }
+
+    let mut indices = Vec::with_capacity(m);
+    let mut i = best_i;
+    let mut j = m;
+    while j > 0 {
+        if from_match[j][i] {
+            indices.push(i - 1);
+            j -= 1;
+            i -= 1;
+        } else {
+            i -= 1;
+        }
+    }
+    indices.reverse();
+
+    Some((best_score as f64, indices))
+}
@@ -0,0 +1,193 @@
+//! On-disk cache of the filesystem schema and desktop apps, so `unified_search`
+//! can score against memory instead of re-walking the home directory on every
+//! keystroke. Built once on startup (or loaded from `dirs::cache_dir()`) and
+//! kept fresh by a periodic background refresh rather than a full rescan.
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::UNIX_EPOCH;
+
+use ignore::{WalkBuilder, WalkState};
+use serde::{Deserialize, Serialize};
+
+use crate::config::CompiledFilter;
+use crate::{collect_desktop_entries, worker_thread_count, AppEntry};
+
+const INDEX_FILE: &str = "index.bin";
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub(crate) struct IndexedEntry {
+    pub path: PathBuf,
+    pub name: String,
+    pub is_dir: bool,
+    pub mtime: u64,
+}
+
+#[derive(Serialize, Deserialize, Default, Clone)]
+pub(crate) struct SearchIndex {
+    pub entries: Vec<IndexedEntry>,
+    pub apps: Vec<AppEntry>,
+    /// mtime (seconds since epoch) of every directory we've indexed, used on
+    /// refresh to figure out which subtrees need re-scanning.
+    dir_mtimes: HashMap<PathBuf, u64>,
+}
+
+fn cache_path() -> PathBuf {
+    dirs::cache_dir()
+        .unwrap_or_else(std::env::temp_dir)
+        .join("hyperlaunch")
+        .join(INDEX_FILE)
+}
+
+pub(crate) fn mtime_secs(meta: &fs::Metadata) -> u64 {
+    meta.modified()
+        .ok()
+        .and_then(|t| t.duration_since(UNIX_EPOCH).ok())
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+impl SearchIndex {
+    /// Load the cached index from disk, falling back to a fresh full walk
+    /// if there's no cache yet (or it fails to parse, e.g. after an upgrade).
+    pub fn load_or_build(filter: &Arc<CompiledFilter>) -> Self {
+        if let Some(index) = Self::load() {
+            return index;
+        }
+        let index = Self::build(filter);
+        index.save();
+        index
+    }
+
+    fn load() -> Option<Self> {
+        let bytes = fs::read(cache_path()).ok()?;
+        bincode::deserialize(&bytes).ok()
+    }
+
+    pub fn save(&self) {
+        let path = cache_path();
+        if let Some(parent) = path.parent() {
+            let _ = fs::create_dir_all(parent);
+        }
+        if let Ok(bytes) = bincode::serialize(self) {
+            let _ = fs::write(path, bytes);
+        }
+    }
+
+    /// Walk every configured root once, collecting every entry plus every
+    /// desktop app. Used on first run and for manual `rebuild_index` calls.
+    pub fn build(filter: &Arc<CompiledFilter>) -> Self {
+        let roots = filter.roots();
+        let threads = worker_thread_count();
+        let (tx, rx) = crossbeam_channel::unbounded();
+
+        let mut builder = WalkBuilder::new(&roots[0]);
+        for root in &roots[1..] {
+            builder.add(root);
+        }
+        let walker = builder
+            .hidden(!filter.include_hidden())
+            .git_ignore(true)
+            .max_depth(Some(filter.max_depth()))
+            .threads(threads)
+            .filter_entry({
+                let filter = Arc::clone(filter);
+                move |e| filter.allows_entry(e)
+            })
+            .build_parallel();
+
+        walker.run(|| {
+            let tx = tx.clone();
+            Box::new(move |entry| {
+                if let Ok(entry) = entry {
+                    let path = entry.path();
+                    if let (Some(name), Ok(meta)) =
+                        (path.file_name().and_then(|n| n.to_str()), entry.metadata())
+                    {
+                        let _ = tx.send(IndexedEntry {
+                            path: path.to_path_buf(),
+                            name: name.to_string(),
+                            is_dir: meta.is_dir(),
+                            mtime: mtime_secs(&meta),
+                        });
+                    }
+                }
+                WalkState::Continue
+            })
+        });
+        drop(tx);
+
+        let entries: Vec<IndexedEntry> = rx.into_iter().collect();
+        let dir_mtimes = entries
+            .iter()
+            .filter(|e| e.is_dir)
+            .map(|e| (e.path.clone(), e.mtime))
+            .collect();
+
+        SearchIndex {
+            entries,
+            apps: collect_desktop_entries(),
+            dir_mtimes,
+        }
+    }
+
+    /// Re-stat every indexed directory; any whose mtime moved has its
+    /// immediate children re-scanned, so renames/creates/deletes show up
+    /// without a full rescan of the home directory.
+    pub fn refresh(&mut self, filter: &CompiledFilter) {
+        self.apps = collect_desktop_entries();
+
+        let mut changed = Vec::new();
+        for (dir, known_mtime) in self.dir_mtimes.iter() {
+            match fs::metadata(dir) {
+                Ok(meta) if meta.is_dir() => {
+                    let current = mtime_secs(&meta);
+                    if current != *known_mtime {
+                        changed.push((dir.clone(), Some(current)));
+                    }
+                }
+                _ => changed.push((dir.clone(), None)), // gone
+            }
+        }
+
+        for (dir, new_mtime) in changed {
+            self.entries.retain(|e| e.path.parent() != Some(dir.as_path()));
+
+            match new_mtime {
+                Some(mtime) => {
+                    if let Ok(read_dir) = fs::read_dir(&dir) {
+                        for entry in read_dir.flatten() {
+                            let path = entry.path();
+                            let Ok(meta) = entry.metadata() else { continue };
+                            let Some(name) = path.file_name().and_then(|n| n.to_str()) else {
+                                continue;
+                            };
+                            if !filter.allows_path(&path, meta.is_dir()) {
+                                continue;
+                            }
+                            let entry_mtime = mtime_secs(&meta);
+                            let is_dir = meta.is_dir();
+                            // Track newly-discovered subdirectories too, or their
+                            // contents would never be re-stated on later refreshes.
+                            if is_dir {
+                                self.dir_mtimes.insert(path.clone(), entry_mtime);
+                            }
+                            self.entries.push(IndexedEntry {
+                                path,
+                                name: name.to_string(),
+                                is_dir,
+                                mtime: entry_mtime,
+                            });
+                        }
+                    }
+                    self.dir_mtimes.insert(dir, mtime);
+                }
+                None => {
+                    self.dir_mtimes.remove(&dir);
+                }
+            }
+        }
+    }
+}
@@ -1,14 +1,24 @@
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
 
+mod config;
+mod fuzzy;
+mod index;
+mod thumbnail;
+
+use arc_swap::ArcSwap;
 use regex::Regex;
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 use std::{fs, path::PathBuf, process::Command};
 use walkdir::WalkDir;
 use base64::Engine as _;
 use base64::engine::general_purpose::STANDARD as BASE64;
-use ignore::WalkBuilder;
-use std::io::{BufRead, BufReader};
-use tauri::Manager;
+use rayon::prelude::*;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, OnceLock};
+use tauri::{Emitter, Manager};
+
+use config::{CompiledFilter, SearchConfig};
+use index::{IndexedEntry, SearchIndex};
 
 #[derive(Serialize, Clone, Debug)]
 struct SearchResult {
@@ -19,10 +29,13 @@ struct SearchResult {
     context: Option<String>, // for content matches: line with matched text
     line_number: Option<usize>, // for content matches
     score: f64, // for sorting
+    match_indices: Vec<usize>, // char indices into `name` (or exec, for apps) to highlight
+    context_before: Vec<String>, // for content matches: lines preceding the match
+    context_after: Vec<String>, // for content matches: lines following the match
 }
 
-#[derive(Serialize, Clone)]
-struct AppEntry {
+#[derive(Serialize, Deserialize, Clone)]
+pub(crate) struct AppEntry {
     name: String,
     exec: String,
     icon: Option<String>,
@@ -176,257 +189,375 @@ fn collect_path_bins() -> Vec<AppEntry> {
     out
 }
 
-fn fuzzy_score(hay: &str, needle: &str) -> f64 {
-    let hay = hay.to_lowercase();
-    let needle = needle.to_lowercase();
-    
-    // Exact match bonus
-    if hay == needle {
-        return 1000.0;
-    }
-    
-    // Prefix match bonus
-    if hay.starts_with(&needle) {
-        return 500.0;
-    }
-    
-    // Word boundary match bonus
-    if hay.split(|c: char| !c.is_alphanumeric()).any(|word| word.starts_with(&needle)) {
-        return 250.0;
-    }
-    
-    // Fuzzy subsequence matching with contiguous bonus
-    let mut i = 0;
-    let mut j = 0;
-    let mut hits = 0;
-    let mut cont = 0;
-    let mut best_cont = 0;
-    let hay_chars: Vec<char> = hay.chars().collect();
-    let needle_chars: Vec<char> = needle.chars().collect();
-    
-    while i < hay_chars.len() && j < needle_chars.len() {
-        if hay_chars[i] == needle_chars[j] {
-            hits += 1;
-            cont += 1;
-            best_cont = best_cont.max(cont);
-            j += 1;
-        } else {
-            cont = 0;
-        }
-        i += 1;
+/// Scores `app`'s name and exec command against `query`, picking whichever
+/// field aligns best so apps can still be found by their launch command.
+fn best_app_match(app: &AppEntry, query: &str) -> Option<(f64, Vec<usize>)> {
+    let name_match = fuzzy::fuzzy_match(&app.name, query);
+    let exec_match = fuzzy::fuzzy_match(&app.exec, query);
+    match (name_match, exec_match) {
+        (Some(n), Some(e)) if e.0 > n.0 => Some(e),
+        (Some(n), _) => Some(n),
+        (None, exec_match) => exec_match,
     }
-    
-    if j == needle_chars.len() {
-        (hits as f64) + (best_cont as f64 * 1.5)
-    } else {
-        -1.0
+}
+
+/// Shared stop flag for the currently in-flight search. Each `unified_search`
+/// call mints a fresh flag and trips the previous one, so a newer keystroke
+/// cancels the in-flight scoring and content scan instead of letting them
+/// run to completion.
+static CURRENT_SEARCH_STOP: OnceLock<Mutex<Option<Arc<AtomicBool>>>> = OnceLock::new();
+
+fn new_search_stop_flag() -> Arc<AtomicBool> {
+    let slot = CURRENT_SEARCH_STOP.get_or_init(|| Mutex::new(None));
+    let mut current = slot.lock().unwrap();
+    if let Some(prev) = current.take() {
+        prev.store(true, Ordering::Relaxed);
     }
+    let fresh = Arc::new(AtomicBool::new(false));
+    *current = Some(Arc::clone(&fresh));
+    fresh
 }
 
-fn search_files_by_name(query: &str, max_results: usize) -> Vec<SearchResult> {
-    let home = dirs::home_dir().unwrap_or_else(|| PathBuf::from("/"));
-    let mut results = Vec::new();
-    let mut count = 0;
-    
-    let walker = WalkBuilder::new(&home)
-        .hidden(false)
-        .git_ignore(true)
-        .max_depth(Some(4)) // Reduced from 6 to 4 for speed
-        .filter_entry(|e| {
-            // Exclude common heavy directories
-            if let Some(name) = e.file_name().to_str() {
-                !matches!(name, 
-                    "node_modules" | ".cargo" | "target" | "build" | "dist" | 
-                    ".npm" | ".cache" | "__pycache__" | ".venv" | "venv" |
-                    ".git" | ".gradle" | ".m2" | ".ivy2" | "pkg" |
-                    "vendor" | "deps" | "Pods" | ".tox" | ".pytest_cache"
-                )
-            } else {
-                true
+fn worker_thread_count() -> usize {
+    std::thread::available_parallelism().map(|n| n.get()).unwrap_or(4)
+}
+
+/// The compiled include/exclude rules, rebuilt only when the user saves a
+/// new config (see `apply_config`) rather than on every search.
+static COMPILED_FILTER: OnceLock<Mutex<Arc<CompiledFilter>>> = OnceLock::new();
+
+fn current_filter() -> Arc<CompiledFilter> {
+    let slot = COMPILED_FILTER
+        .get_or_init(|| Mutex::new(Arc::new(CompiledFilter::from_config(&SearchConfig::load()))));
+    Arc::clone(&slot.lock().unwrap())
+}
+
+fn apply_config(config: SearchConfig) {
+    config.save();
+    let compiled = Arc::new(CompiledFilter::from_config(&config));
+    *COMPILED_FILTER
+        .get_or_init(|| Mutex::new(Arc::clone(&compiled)))
+        .lock()
+        .unwrap() = compiled;
+}
+
+/// Lazily loads (or builds, on first run) the cached filesystem/app index and
+/// keeps it resident for the lifetime of the process.
+///
+/// `ArcSwap` rather than a `Mutex`: a query only needs a cheap `Arc` snapshot
+/// to score against, so a rebuild/refresh swapping in a fresh index never
+/// blocks (or gets blocked by) an in-flight `unified_search` the way holding
+/// a `Mutex` for the whole query would.
+static SEARCH_INDEX: OnceLock<ArcSwap<SearchIndex>> = OnceLock::new();
+
+fn search_index() -> &'static ArcSwap<SearchIndex> {
+    SEARCH_INDEX.get_or_init(|| ArcSwap::from_pointee(SearchIndex::load_or_build(&current_filter())))
+}
+
+/// Scores the cached index entries against `query` without walking the
+/// filesystem; the walk itself only happens when the index is built or
+/// refreshed. The only disk I/O here is thumbnail generation for the kept
+/// results, when thumbnails are enabled.
+fn search_files_by_name(
+    entries: &[IndexedEntry],
+    query: &str,
+    max_results: usize,
+    filter: &CompiledFilter,
+    stop: &Arc<AtomicBool>,
+) -> Vec<SearchResult> {
+    let mut scored: Vec<SearchResult> = entries
+        .par_iter()
+        .filter_map(|entry| {
+            if stop.load(Ordering::Relaxed) {
+                return None;
             }
+            let (score, match_indices) = fuzzy::fuzzy_match(&entry.name, query)?;
+            Some(SearchResult {
+                result_type: if entry.is_dir { "directory" } else { "file" }.to_string(),
+                name: entry.name.clone(),
+                path: entry.path.to_string_lossy().to_string(),
+                icon_data: None,
+                context: None,
+                line_number: None,
+                score,
+                match_indices,
+                context_before: Vec::new(),
+                context_after: Vec::new(),
+            })
         })
-        .build();
-    
-    for entry in walker.filter_map(|e| e.ok()) {
-        count += 1;
-        // Limit iterations for speed
-        if count > 5000 {
-            break;
-        }
-        
-        let path = entry.path();
-        if let Some(name) = path.file_name().and_then(|n| n.to_str()) {
-            let score = fuzzy_score(name, query);
-            if score >= 0.0 {
-                let result_type = if path.is_dir() { "directory" } else { "file" };
-                results.push(SearchResult {
-                    result_type: result_type.to_string(),
-                    name: name.to_string(),
-                    path: path.to_string_lossy().to_string(),
-                    icon_data: None,
-                    context: None,
-                    line_number: None,
-                    score,
-                });
-                
-                // Early exit if we have enough good results
-                if results.len() >= max_results * 2 {
-                    break;
+        .collect();
+
+    scored.sort_by(|a, b| b.score.partial_cmp(&a.score).unwrap());
+    scored.truncate(max_results);
+
+    // Thumbnails are only worth generating for results we'll actually show,
+    // and run in parallel on these same worker threads so decodes overlap
+    // instead of running one after another; this returns once every decode
+    // has finished. `thumbnail_for` stats the file itself for its
+    // cache key, since the index's mtime can go stale for in-place edits.
+    if filter.generate_thumbnails() {
+        let max_source_size = filter.thumbnail_max_source_size();
+        scored.par_iter_mut().for_each(|result| {
+            if result.result_type == "file" {
+                let path = std::path::Path::new(&result.path);
+                if thumbnail::is_thumbnailable(path) {
+                    result.icon_data = thumbnail::thumbnail_for(path, max_source_size);
                 }
             }
+        });
+        // Persist once for the whole batch instead of on every thumbnail, or
+        // concurrent searches would serialize behind repeated full-cache writes.
+        thumbnail::flush_cache();
+    }
+
+    scored
+}
+
+const CONTENT_CONTEXT_LINES: usize = 2;
+const CONTENT_MAX_MATCHES_PER_FILE: usize = 5;
+const CONTENT_CONTEXT_MAX_CHARS: usize = 200;
+const BINARY_SNIFF_BYTES: usize = 512;
+
+/// How the content search should treat `query`: a regex wrapped in
+/// `/slashes/`, or otherwise a smart-case substring (case-insensitive unless
+/// the query itself contains an uppercase letter, ripgrep-style).
+enum QueryMode {
+    Regex(Regex),
+    SmartCase(String),
+}
+
+fn parse_query_mode(query: &str) -> QueryMode {
+    if query.len() > 2 && query.starts_with('/') && query.ends_with('/') {
+        if let Ok(re) = Regex::new(&query[1..query.len() - 1]) {
+            return QueryMode::Regex(re);
         }
     }
-    
-    results.sort_by(|a, b| b.score.partial_cmp(&a.score).unwrap());
-    results.truncate(max_results);
-    results
+    QueryMode::SmartCase(query.to_string())
 }
 
-fn search_file_contents(query: &str, max_results: usize) -> Vec<SearchResult> {
-    let home = dirs::home_dir().unwrap_or_else(|| PathBuf::from("/"));
-    let mut results = Vec::new();
-    let query_lower = query.to_lowercase();
-    let mut files_checked = 0;
-    
-    let walker = WalkBuilder::new(&home)
-        .hidden(false)
-        .git_ignore(true)
-        .max_depth(Some(3)) // Reduced from 5 to 3 for speed
-        .filter_entry(|e| {
-            // Exclude common heavy directories
-            if let Some(name) = e.file_name().to_str() {
-                !matches!(name, 
-                    "node_modules" | ".cargo" | "target" | "build" | "dist" | 
-                    ".npm" | ".cache" | "__pycache__" | ".venv" | "venv" |
-                    ".git" | ".gradle" | ".m2" | ".ivy2" | "pkg" |
-                    "vendor" | "deps" | "Pods" | ".tox" | ".pytest_cache"
-                )
+fn line_matches(line: &str, mode: &QueryMode) -> bool {
+    match mode {
+        QueryMode::Regex(re) => re.is_match(line),
+        QueryMode::SmartCase(needle) => {
+            if needle.chars().any(|c| c.is_uppercase()) {
+                line.contains(needle.as_str())
             } else {
-                true
+                line.to_lowercase().contains(&needle.to_lowercase())
+            }
+        }
+    }
+}
+
+/// Truncates `line` to `max_chars` on a char boundary (not a byte index,
+/// which can panic on multibyte UTF-8) and marks truncation with an ellipsis.
+fn truncate_for_context(line: &str, max_chars: usize) -> String {
+    if line.chars().count() > max_chars {
+        let mut truncated: String = line.chars().take(max_chars).collect();
+        truncated.push_str("...");
+        truncated
+    } else {
+        line.to_string()
+    }
+}
+
+/// Sniffs the first chunk of a file for NUL bytes, the same heuristic
+/// ripgrep/fd use to skip binary files regardless of extension or size.
+fn looks_binary(path: &std::path::Path) -> bool {
+    use std::io::Read;
+    let Ok(mut file) = fs::File::open(path) else {
+        return true;
+    };
+    let mut buf = [0u8; BINARY_SNIFF_BYTES];
+    let Ok(n) = file.read(&mut buf) else {
+        return true;
+    };
+    buf[..n].contains(&0)
+}
+
+/// Greps the cached index's file paths for `query`, in parallel across
+/// worker threads. This still has to open and read each candidate file, but
+/// skips re-walking the directory tree to find them.
+fn search_file_contents(
+    entries: &[IndexedEntry],
+    query: &str,
+    max_results: usize,
+    stop: Arc<AtomicBool>,
+    max_file_size: u64,
+) -> Vec<SearchResult> {
+    let mode = parse_query_mode(query);
+
+    let mut results: Vec<SearchResult> = entries
+        .par_iter()
+        .filter(|entry| !entry.is_dir)
+        .flat_map(|entry| {
+            if stop.load(Ordering::Relaxed) {
+                return Vec::new();
             }
+            scan_file_for_matches(&entry.path, &mode, max_file_size)
         })
-        .build();
-    
-    for entry in walker.filter_map(|e| e.ok()) {
-        let path = entry.path();
-        
-        // Only search text files
-        if !path.is_file() {
-            continue;
+        .collect();
+
+    results.truncate(max_results);
+    results
+}
+
+/// Greps a single file for `mode`, returning up to `CONTENT_MAX_MATCHES_PER_FILE`
+/// matches, each carrying its surrounding context lines.
+fn scan_file_for_matches(path: &std::path::Path, mode: &QueryMode, max_file_size: u64) -> Vec<SearchResult> {
+    if let Ok(meta) = path.metadata() {
+        if meta.len() > max_file_size {
+            return Vec::new();
         }
-        
-        files_checked += 1;
-        // Limit number of files to check for speed
-        if files_checked > 2000 {
+    } else {
+        return Vec::new();
+    }
+
+    if looks_binary(path) {
+        return Vec::new();
+    }
+
+    let Ok(content) = fs::read_to_string(path) else {
+        return Vec::new();
+    };
+    let lines: Vec<&str> = content.lines().collect();
+    let name = path
+        .file_name()
+        .and_then(|n| n.to_str())
+        .unwrap_or("unknown")
+        .to_string();
+
+    let mut results = Vec::new();
+    for (line_num, line) in lines.iter().enumerate() {
+        if results.len() >= CONTENT_MAX_MATCHES_PER_FILE {
             break;
         }
-        
-        // Skip large files
-        if let Ok(meta) = path.metadata() {
-            if meta.len() > 500_000 { // 500KB limit
-                continue;
-            }
-        }
-        
-        // Try to read as text
-        if let Ok(file) = fs::File::open(path) {
-            let reader = BufReader::new(file);
-            
-            for (line_num, line_result) in reader.lines().enumerate().take(500) {
-                if let Ok(line) = line_result {
-                    if line.to_lowercase().contains(&query_lower) {
-                        // Create context with the matched line
-                        let context = if line.len() > 100 {
-                            format!("{}...", &line[..100])
-                        } else {
-                            line.clone()
-                        };
-                        
-                        results.push(SearchResult {
-                            result_type: "content".to_string(),
-                            name: path.file_name()
-                                .and_then(|n| n.to_str())
-                                .unwrap_or("unknown")
-                                .to_string(),
-                            path: path.to_string_lossy().to_string(),
-                            icon_data: None,
-                            context: Some(context),
-                            line_number: Some(line_num + 1),
-                            score: 10.0,
-                        });
-                        
-                        break; // Only show first match per file
-                    }
-                }
-            }
-            
-            if results.len() >= max_results {
-                break;
-            }
+        if !line_matches(line, mode) {
+            continue;
         }
+
+        let before_start = line_num.saturating_sub(CONTENT_CONTEXT_LINES);
+        let context_before = lines[before_start..line_num]
+            .iter()
+            .map(|l| truncate_for_context(l, CONTENT_CONTEXT_MAX_CHARS))
+            .collect();
+        let after_end = (line_num + 1 + CONTENT_CONTEXT_LINES).min(lines.len());
+        let context_after = lines[line_num + 1..after_end]
+            .iter()
+            .map(|l| truncate_for_context(l, CONTENT_CONTEXT_MAX_CHARS))
+            .collect();
+
+        results.push(SearchResult {
+            result_type: "content".to_string(),
+            name: name.clone(),
+            path: path.to_string_lossy().to_string(),
+            icon_data: None,
+            context: Some(truncate_for_context(line, CONTENT_CONTEXT_MAX_CHARS)),
+            line_number: Some(line_num + 1),
+            score: 10.0,
+            match_indices: Vec::new(),
+            context_before,
+            context_after,
+        });
     }
-    
-    results.truncate(max_results);
+
     results
 }
 
+/// One query's monotonically increasing id, so the frontend can discard
+/// batches that arrive after a newer keystroke superseded them.
+static NEXT_QUERY_ID: AtomicU64 = AtomicU64::new(1);
+
+fn next_query_id() -> u64 {
+    NEXT_QUERY_ID.fetch_add(1, Ordering::Relaxed)
+}
+
+#[derive(Serialize, Clone)]
+struct SearchBatch {
+    query_id: u64,
+    results: Vec<SearchResult>,
+}
+
+#[derive(Serialize, Clone)]
+struct SearchDone {
+    query_id: u64,
+}
+
+/// Runs each search source in turn and emits its batch as soon as it's
+/// ready — apps first (in-memory), then filenames, then content — instead
+/// of blocking the caller until the slowest stage finishes.
 #[tauri::command]
-fn unified_search(query: String) -> Vec<SearchResult> {
+fn unified_search(app: tauri::AppHandle, query: String) -> u64 {
+    let query_id = next_query_id();
+    let stop = new_search_stop_flag();
+
     if query.trim().is_empty() {
-        return Vec::new();
+        let _ = app.emit("search://done", SearchDone { query_id });
+        return query_id;
     }
-    
-    let mut all_results = Vec::new();
-    
-    // 1. Search apps (in memory, very fast)
-    let apps = collect_desktop_entries();
-    for app in apps {
-        let name_score = fuzzy_score(&app.name, &query);
-        let exec_score = fuzzy_score(&app.exec, &query);
-        let score = name_score.max(exec_score);
-        
-        if score >= 0.0 {
-            all_results.push(SearchResult {
-                result_type: "app".to_string(),
-                name: app.name.clone(),
-                path: app.exec.clone(),
-                icon_data: app.icon_data.clone(),
-                context: Some(app.exec.clone()),
-                line_number: None,
-                score: score * 100.0, // Massive boost for apps (was 10.0)
-            });
+
+    std::thread::spawn(move || {
+        let filter = current_filter();
+        // A snapshot `Arc`, not a lock: a rebuild/refresh swapping in a fresh
+        // index doesn't block this query, and this query doesn't block them.
+        let index = search_index().load_full();
+
+        // 1. Search apps (in memory, very fast)
+        let mut app_results: Vec<SearchResult> = index
+            .apps
+            .iter()
+            .filter_map(|app_entry| {
+                let (score, match_indices) = best_app_match(app_entry, &query)?;
+                Some(SearchResult {
+                    result_type: "app".to_string(),
+                    name: app_entry.name.clone(),
+                    path: app_entry.exec.clone(),
+                    icon_data: app_entry.icon_data.clone(),
+                    context: Some(app_entry.exec.clone()),
+                    line_number: None,
+                    score: score * 100.0, // Massive boost for apps (was 10.0)
+                    match_indices,
+                    context_before: Vec::new(),
+                    context_after: Vec::new(),
+                })
+            })
+            .collect();
+        app_results.sort_by(|a, b| b.score.partial_cmp(&a.score).unwrap());
+        let _ = app.emit("search://results", SearchBatch { query_id, results: app_results });
+
+        // 2. Search files by name against the cached index (reduced from 30 to 20)
+        let file_results: Vec<SearchResult> = search_files_by_name(&index.entries, &query, 20, &filter, &stop)
+            .into_iter()
+            .map(|mut result| {
+                // Heavily penalize config directories
+                let penalty = if result.path.contains("/.config/") ||
+                                 result.path.contains("/.local/") ||
+                                 result.path.contains("/.cache/") {
+                    0.1 // 10x penalty for config dirs
+                } else {
+                    1.0
+                };
+                result.score *= 5.0 * penalty; // Boost file name matches but apply penalty
+                result
+            })
+            .collect();
+        let _ = app.emit("search://results", SearchBatch { query_id, results: file_results });
+
+        // 3. Search file contents (only if query is 4+ chars, reduced from 3)
+        if query.len() >= 4 && !stop.load(Ordering::Relaxed) {
+            let content_results = search_file_contents(
+                &index.entries,
+                &query,
+                15, // Reduced from 20 to 15
+                Arc::clone(&stop),
+                filter.max_content_file_size(),
+            );
+            let _ = app.emit("search://results", SearchBatch { query_id, results: content_results });
         }
-    }
-    
-    // 2. Search files by name (reduced from 30 to 20)
-    let file_results = search_files_by_name(&query, 20);
-    for mut result in file_results {
-        // Heavily penalize config directories
-        let penalty = if result.path.contains("/.config/") || 
-                         result.path.contains("/.local/") ||
-                         result.path.contains("/.cache/") {
-            0.1 // 10x penalty for config dirs
-        } else {
-            1.0
-        };
-        
-        result.score *= 5.0 * penalty; // Boost file name matches but apply penalty
-        all_results.push(result);
-    }
-    
-    // 3. Search file contents (only if query is 4+ chars, reduced from 3)
-    if query.len() >= 4 {
-        let content_results = search_file_contents(&query, 15); // Reduced from 20 to 15
-        all_results.extend(content_results);
-    }
-    
-    // Sort by score
-    all_results.sort_by(|a, b| b.score.partial_cmp(&a.score).unwrap());
-    
-    // Limit total results (reduced from 100 to 50)
-    all_results.truncate(50);
-    
-    all_results
+
+        let _ = app.emit("search://done", SearchDone { query_id });
+    });
+
+    query_id
 }
 
 #[tauri::command]
@@ -479,7 +610,59 @@ fn ensure_focus(app: tauri::AppHandle) {
     }
 }
 
+/// Forces a full rescan of the configured search roots and desktop apps, bypassing
+/// the incremental refresh. Exposed for manual invalidation from the UI.
+#[tauri::command]
+fn rebuild_index() {
+    let fresh = SearchIndex::build(&current_filter());
+    fresh.save();
+    search_index().store(Arc::new(fresh));
+}
+
+/// Reads the saved config from disk, or the defaults if none exists yet.
+#[tauri::command]
+fn get_config() -> SearchConfig {
+    SearchConfig::load()
+}
+
+/// Saves a new config and recompiles the filter used by the index build,
+/// refresh, and content search. If `included_dirs` or `excluded_dirs`
+/// changed, kicks off a background `rebuild_index`: the incremental
+/// refresh only re-stats directories already present in `dir_mtimes`, so it
+/// would never notice a newly-included root or drop a newly-excluded one.
+#[tauri::command]
+fn set_config(new_config: SearchConfig) {
+    let previous = SearchConfig::load();
+    let scope_changed = previous.included_dirs != new_config.included_dirs
+        || previous.excluded_dirs != new_config.excluded_dirs;
+
+    apply_config(new_config);
+
+    if scope_changed {
+        std::thread::spawn(rebuild_index);
+    }
+}
+
+/// Periodically re-stats indexed directories and writes the refreshed index
+/// back to disk, so the cache stays close to the real filesystem without a
+/// full rescan on every launch. Refreshes a cloned snapshot and swaps it in
+/// when done, so it never competes with an in-flight `unified_search` for a
+/// lock.
+fn spawn_index_refresh_task() {
+    std::thread::spawn(|| loop {
+        std::thread::sleep(std::time::Duration::from_secs(300));
+        let mut next = (*search_index().load_full()).clone();
+        next.refresh(&current_filter());
+        next.save();
+        search_index().store(Arc::new(next));
+    });
+}
+
 fn main() {
+    // Warm the index before the UI can issue its first search.
+    let _ = search_index();
+    spawn_index_refresh_task();
+
     tauri::Builder::default()
         .setup(|_app| {
             // Devtools disabled - was causing focus issues on startup
@@ -489,7 +672,7 @@ fn main() {
             // }
             Ok(())
         })
-        .invoke_handler(tauri::generate_handler![list_apps, launch, exit_app, unified_search, ensure_focus])
+        .invoke_handler(tauri::generate_handler![list_apps, launch, exit_app, unified_search, ensure_focus, rebuild_index, get_config, set_config])
         .plugin(tauri_plugin_window_state::Builder::default().build())
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
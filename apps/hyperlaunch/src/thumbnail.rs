@@ -0,0 +1,114 @@
+//! Small bounded-size PNG thumbnails for image file results, so the UI can
+//! preview them the same way `icon_to_data_url` previews app icons. Decoding
+//! and downscaling happens on the search worker threads; results are cached
+//! on disk by path+mtime next to the file index, so re-searching the same
+//! file doesn't re-decode it.
+
+use std::collections::HashMap;
+use std::fs;
+use std::io::Cursor;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Mutex, OnceLock};
+
+use base64::Engine as _;
+use image::imageops::FilterType;
+use image::ImageFormat;
+
+use crate::index::mtime_secs;
+use crate::BASE64;
+
+pub(crate) const THUMBNAIL_SIZE: u32 = 64;
+
+const THUMBNAIL_CACHE_FILE: &str = "thumbnails.bin";
+
+const IMAGE_EXTENSIONS: &[&str] = &["png", "jpg", "jpeg", "gif", "bmp", "ico", "tiff", "tif", "webp"];
+
+pub(crate) fn is_thumbnailable(path: &Path) -> bool {
+    path.extension()
+        .and_then(|e| e.to_str())
+        .map(|e| IMAGE_EXTENSIONS.contains(&e.to_lowercase().as_str()))
+        .unwrap_or(false)
+}
+
+fn cache_path() -> PathBuf {
+    dirs::cache_dir()
+        .unwrap_or_else(std::env::temp_dir)
+        .join("hyperlaunch")
+        .join(THUMBNAIL_CACHE_FILE)
+}
+
+type ThumbnailCache = HashMap<PathBuf, (u64, String)>;
+
+static CACHE: OnceLock<Mutex<ThumbnailCache>> = OnceLock::new();
+static CACHE_DIRTY: AtomicBool = AtomicBool::new(false);
+
+fn cache() -> &'static Mutex<ThumbnailCache> {
+    CACHE.get_or_init(|| {
+        let loaded = fs::read(cache_path())
+            .ok()
+            .and_then(|bytes| bincode::deserialize(&bytes).ok())
+            .unwrap_or_default();
+        Mutex::new(loaded)
+    })
+}
+
+/// Persists the in-memory cache to disk if any thumbnail was generated since
+/// the last flush. Callers should invoke this once after a batch of
+/// `thumbnail_for` calls (e.g. after a `par_iter_mut` pass) rather than
+/// having every generated thumbnail rewrite the whole cache file itself.
+pub(crate) fn flush_cache() {
+    if CACHE_DIRTY.swap(false, Ordering::SeqCst) {
+        save_cache(&cache().lock().unwrap());
+    }
+}
+
+fn save_cache(cache: &ThumbnailCache) {
+    let path = cache_path();
+    if let Some(parent) = path.parent() {
+        let _ = fs::create_dir_all(parent);
+    }
+    if let Ok(bytes) = bincode::serialize(cache) {
+        let _ = fs::write(path, bytes);
+    }
+}
+
+/// Returns a `data:image/png;base64,...` thumbnail for `path`, generating
+/// and caching it (keyed by path+mtime) if it isn't cached already. Skips
+/// files over `max_source_size` so a giant image can't stall a search.
+///
+/// Stats the file itself for the cache key rather than trusting the index's
+/// `mtime`: `SearchIndex::refresh` only re-stats a *parent directory* when
+/// its mtime changes, so a file overwritten in place (e.g. `cp -f`, an
+/// editor save) can leave the index's stored mtime stale while the file's
+/// own mtime moves — the index value alone would keep serving a stale
+/// thumbnail forever.
+pub(crate) fn thumbnail_for(path: &Path, max_source_size: u64) -> Option<String> {
+    let meta = path.metadata().ok()?;
+    let mtime = mtime_secs(&meta);
+
+    if let Some((cached_mtime, data_url)) = cache().lock().unwrap().get(path) {
+        if *cached_mtime == mtime {
+            return Some(data_url.clone());
+        }
+    }
+
+    if meta.len() > max_source_size {
+        return None;
+    }
+
+    let source = image::open(path).ok()?;
+    let thumbnail = source.resize(THUMBNAIL_SIZE, THUMBNAIL_SIZE, FilterType::Triangle);
+
+    let mut buf = Vec::new();
+    thumbnail.write_to(&mut Cursor::new(&mut buf), ImageFormat::Png).ok()?;
+    let data_url = format!("data:image/png;base64,{}", BASE64.encode(&buf));
+
+    cache()
+        .lock()
+        .unwrap()
+        .insert(path.to_path_buf(), (mtime, data_url.clone()));
+    CACHE_DIRTY.store(true, Ordering::SeqCst);
+
+    Some(data_url)
+}